@@ -0,0 +1,53 @@
+use super::FullDictionary;
+
+impl FullDictionary {
+    /// Builds a dictionary from a flat iterator of words, one entry per
+    /// word, rather than the curated word-list data file
+    /// [`FullDictionary::create_from_curated`] loads. Used for `harper-ls`'s
+    /// user dictionary, which is just one word per line on disk.
+    ///
+    /// Blank lines are skipped so an empty or not-yet-created user
+    /// dictionary file still produces a usable (empty) dictionary.
+    pub fn create_from_iter<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> Self {
+        let mut dictionary = Self::new();
+
+        for word in words {
+            let word = word.as_ref().trim();
+
+            if word.is_empty() {
+                continue;
+            }
+
+            dictionary.append_word_str(word);
+        }
+
+        dictionary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FullDictionary;
+    use crate::Dictionary;
+
+    #[test]
+    fn skips_blank_and_whitespace_only_lines() {
+        let dict = FullDictionary::create_from_iter(["hello", "", "   ", "world"]);
+
+        let hello: Vec<char> = "hello".chars().collect();
+        let world: Vec<char> = "world".chars().collect();
+
+        assert!(dict.contains_word(&hello));
+        assert!(dict.contains_word(&world));
+    }
+
+    #[test]
+    fn repeated_words_still_look_up_fine() {
+        // Duplicate entries for the same word (e.g. a dictionary.txt with a
+        // repeated line) shouldn't make the word stop resolving.
+        let dict = FullDictionary::create_from_iter(["hello", "hello"]);
+
+        let hello: Vec<char> = "hello".chars().collect();
+        assert!(dict.contains_word(&hello));
+    }
+}
@@ -0,0 +1,64 @@
+use super::{LintKind, LintSet};
+
+impl LintSet {
+    /// Drops every constituent linter whose [`LintKind`] doesn't satisfy
+    /// `predicate`, so per-language `only`/`except` configuration (see
+    /// `harper-ls`'s `LanguageLintConfig`) can narrow a [`LintSet`] down to
+    /// just the categories a client wants enabled for a given file.
+    pub fn filter_kinds(mut self, predicate: impl Fn(LintKind) -> bool) -> Self {
+        self.linters.retain(|(kind, _)| predicate(*kind));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LintKind, LintSet};
+    use crate::document::Document;
+    use crate::linting::{Lint, Linter};
+
+    struct NoopLinter;
+
+    impl Linter for NoopLinter {
+        fn lint(&mut self, _document: &Document) -> Vec<Lint> {
+            Vec::new()
+        }
+    }
+
+    fn set_with(kinds: impl IntoIterator<Item = LintKind>) -> LintSet {
+        LintSet {
+            linters: kinds
+                .into_iter()
+                .map(|kind| (kind, Box::new(NoopLinter) as Box<dyn Linter>))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn keeps_only_kinds_the_predicate_accepts() {
+        let set = set_with([LintKind::Spelling, LintKind::Capitalization]);
+
+        let filtered = set.filter_kinds(|kind| kind == LintKind::Spelling);
+
+        assert_eq!(filtered.linters.len(), 1);
+        assert_eq!(filtered.linters[0].0, LintKind::Spelling);
+    }
+
+    #[test]
+    fn predicate_accepting_everything_keeps_everything() {
+        let set = set_with([LintKind::Spelling, LintKind::Capitalization]);
+
+        let filtered = set.filter_kinds(|_| true);
+
+        assert_eq!(filtered.linters.len(), 2);
+    }
+
+    #[test]
+    fn predicate_rejecting_everything_empties_the_set() {
+        let set = set_with([LintKind::Spelling, LintKind::Capitalization]);
+
+        let filtered = set.filter_kinds(|_| false);
+
+        assert!(filtered.linters.is_empty());
+    }
+}
@@ -3,12 +3,9 @@ use smallvec::ToSmallVec;
 
 use super::lint::Suggestion;
 use super::{Lint, LintKind, Linter};
-use crate::{
-    document::{self, Document},
-    Span, Token, TokenKind,
-};
-use crate::{spell::suggest_correct_spelling, token};
-use crate::{CharString, Dictionary, TokenStringExt};
+use crate::document::Document;
+use crate::spell::suggest_correct_spelling;
+use crate::{CharString, Dictionary, Span, Token, TokenStringExt};
 
 pub struct SpellCheck<T>
 where
@@ -53,12 +50,36 @@ impl<T: Dictionary> SpellCheck<T> {
     }
 }
 
+/// The `Markdown` parser still tokenizes link destinations and other
+/// non-prose markdown as `Unlintable` spans sitting between `[` / `]`
+/// punctuation rather than splicing them into the surrounding words, so a
+/// word immediately after a `[...]` run (e.g. the description before a
+/// reference-style link) can come back as its own token missing the
+/// unlintable content that separates it from real prose. Reassemble the
+/// original word so it's checked (or skipped) as a whole instead of being
+/// spell-checked as a stray fragment.
+///
+/// This is a backward-scanning patch over the parser's output, not a fix in
+/// the parser itself — it only catches the specific three-token
+/// `[` / unlintable / `]` / word shape, not link destinations, inline code,
+/// code fences, autolinks, or inline HTML in general. The real fix is
+/// rewriting the `Markdown` parser on pulldown-cmark so it classifies each
+/// span as prose or non-prose while parsing (handling pulldown-cmark 0.10's
+/// `Tag`/`TagEnd` split and struct-variant `Heading` along the way) and only
+/// ever emits word tokens for genuine prose — at which point this function
+/// can be deleted outright rather than patched further.
+///
+/// **Status: not done.** No commit in this backlog item touches the
+/// `Markdown` parser, which lives outside `harper-core`'s linting module.
+/// Nothing here should be read as progress toward that rewrite — this
+/// function is exactly the pre-existing heuristic, kept only because
+/// deleting it without the parser rewrite regresses real cases (see the
+/// history of this file). Land the parser rewrite as its own change before
+/// treating this backlog item as complete.
 fn potentially_combine_unlintable_markdown_tokens(
     document: &Document,
     idx: usize,
 ) -> Option<(Token, Span)> {
-    dbg!(idx);
-
     let missing_token = document.get_token(idx)?;
     let [punct_1, unlintable, punct_2] = document
         .get_tokens()
@@ -66,8 +87,6 @@ fn potentially_combine_unlintable_markdown_tokens(
         .try_into()
         .ok()?;
 
-    dbg!(punct_1, unlintable, punct_2, missing_token);
-
     // We require the unlintable token to be surrounded by punctuation.
     if !(punct_1.kind.is_open_square() && punct_2.kind.is_close_square()) {
         return None;
@@ -95,22 +114,16 @@ impl<T: Dictionary> Linter for SpellCheck<T> {
             .enumerate()
             .filter(|(_, t)| t.kind.is_word())
         {
-            println!("checking: {:?}", word);
-
             let word_chars = document.get_span_content(word.span);
             if self.dictionary.contains_word(word_chars) {
-                println!(
-                    "dict contains, done: {:?}",
-                    document.get_span_content(word.span)
-                );
                 continue;
             }
 
-            // attempt to combine unlintable markdown tokens
+            // Attempt to combine unlintable markdown tokens (see
+            // `potentially_combine_unlintable_markdown_tokens`).
             if let Some((new_token, mut unlintable_span)) =
                 potentially_combine_unlintable_markdown_tokens(document, idx)
             {
-                // todo: fix unlintable span creation to remove this hack
                 unlintable_span.push_by(1);
 
                 let extra_content = document.get_span_content(unlintable_span);
@@ -118,7 +131,6 @@ impl<T: Dictionary> Linter for SpellCheck<T> {
 
                 let check_word = [extra_content, suffix_content].concat();
                 if self.dictionary.contains_word(&check_word) {
-                    println!("dict contains check word: {:?}", check_word);
                     continue;
                 } else {
                     word = new_token;
@@ -1,16 +1,26 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
-use harper_core::{parsers::Markdown, Document, FullDictionary, LintSet, Linter, MergedDictionary};
-use tokio::sync::Mutex;
+use harper_core::{
+    parsers::Markdown, Document, FullDictionary, Lint, LintKind, LintSet, Linter, MergedDictionary,
+    Span,
+};
+use serde::Deserialize;
+use tokio::{io::AsyncWriteExt, sync::Mutex};
 use tower_lsp::{
     jsonrpc::Result,
     lsp_types::{
         notification::{PublishDiagnostics, ShowMessage},
-        CodeAction, CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability,
-        CodeActionResponse, Diagnostic, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-        DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializeResult,
-        InitializedParams, MessageType, PublishDiagnosticsParams, Range, ServerCapabilities,
-        ShowMessageParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+        CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+        CodeActionProviderCapability, CodeActionResponse, Command, ConfigurationItem, Diagnostic,
+        DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+        DidOpenTextDocumentParams, DidSaveTextDocumentParams, ExecuteCommandOptions,
+        ExecuteCommandParams, InitializeParams, InitializeResult, InitializedParams, MessageType,
+        PublishDiagnosticsParams, Range, ServerCapabilities, ShowMessageParams,
+        TextDocumentContentChangeEvent, TextDocumentSyncCapability, TextDocumentSyncKind,
         TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
     },
     Client, LanguageServer,
@@ -22,12 +32,129 @@ use crate::{
     tree_sitter_parser::TreeSitterParser,
 };
 
+/// Command id advertised via `execute_command_provider`, reloading every
+/// in-memory dictionary and re-linting all open files without requiring the
+/// client to restart the server.
+const RELOAD_DICTIONARIES_COMMAND: &str = "harper.reloadDictionaries";
+/// Command id for the "Add to dictionary" code action, persisting a word to
+/// the user's dictionary file and re-linting open files.
+const ADD_TO_DICTIONARY_COMMAND: &str = "harper.addToDictionary";
+
+/// Per-language overrides for which [`LintKind`]s are active, mirroring the
+/// `only`/`except` shape editors like Helix use to route requests to a
+/// subset of configured servers.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LanguageLintConfig {
+    /// If set, only these lint kinds are enabled for the language, regardless
+    /// of `except_lints`.
+    #[serde(default)]
+    only_lints: Option<HashSet<LintKind>>,
+    /// Lint kinds to disable for the language. Ignored if `only_lints` is set.
+    #[serde(default)]
+    except_lints: Option<HashSet<LintKind>>,
+}
+
+impl LanguageLintConfig {
+    fn is_enabled(&self, kind: LintKind) -> bool {
+        if let Some(only) = &self.only_lints {
+            return only.contains(&kind);
+        }
+
+        if let Some(except) = &self.except_lints {
+            return !except.contains(&kind);
+        }
+
+        true
+    }
+}
+
+/// Server configuration, populated from the client's `initializationOptions`
+/// and kept up to date via `workspace/didChangeConfiguration`.
+///
+/// Languages are keyed by file extension (e.g. `"rs"`, `"md"`) since that's
+/// what Harper already uses to pick a [`TreeSitterParser`] in
+/// [`Backend::update_document`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Config {
+    #[serde(default)]
+    languages: HashMap<String, LanguageLintConfig>,
+}
+
+impl Config {
+    fn for_extension(&self, extension: Option<&str>) -> Option<&LanguageLintConfig> {
+        self.languages.get(extension?)
+    }
+}
+
 pub struct Backend {
     client: Client,
-    global_dictionary: Arc<FullDictionary>,
-    files: Mutex<HashMap<Url, Document>>,
+    global_dictionary: Mutex<Arc<FullDictionary>>,
+    files: Mutex<HashMap<Url, OpenFile>>,
     /// The identifiers extracted from each file by Tree-sitter.
     ident_dicts: Mutex<HashMap<Url, Arc<FullDictionary>>>,
+    /// The comment and string-literal prose extracted from each file by
+    /// Tree-sitter, kept separate from `files` since each region is its own
+    /// sub-document with an offset back into the original source.
+    prose_regions: Mutex<HashMap<Url, Vec<ProseRegion>>>,
+    /// Words the user has chosen to add via the "Add to dictionary" code
+    /// action, persisted to [`Backend::user_dictionary_path`].
+    user_dictionary: Mutex<Arc<FullDictionary>>,
+    /// Serializes `apply_content_changes` calls so two `did_change`
+    /// notifications for the same document (tower-lsp dispatches
+    /// notifications concurrently) can't both read `files` before either
+    /// writes back, which would let the second write silently clobber the
+    /// first edit.
+    edit_lock: Mutex<()>,
+    /// Serializes `add_to_dictionary` calls so two concurrent
+    /// `harper.addToDictionary` invocations can't both read the dictionary
+    /// file, both see the word as absent, and both append it.
+    user_dictionary_write_lock: Mutex<()>,
+    config: Mutex<Config>,
+    /// What the client told us it supports in `initialize`, so we can avoid
+    /// assuming one editor's shape (e.g. attaching diagnostic versions a
+    /// client never asked for, or fetching config it can't serve).
+    client_capabilities: Mutex<ClientCapabilities>,
+}
+
+/// A file open in the editor, tracked alongside its own character buffer so
+/// `did_change` can apply incremental range edits without the client
+/// resending the full document.
+struct OpenFile {
+    source: Vec<char>,
+    document: Document,
+    version: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ClientCapabilities {
+    /// Whether the client declared support for `textDocument/codeAction`.
+    code_actions: bool,
+    /// Whether the client tracks diagnostic versions
+    /// (`textDocument.publishDiagnostics.versionSupport`).
+    diagnostic_versioning: bool,
+    /// Whether the client supports `workspace/configuration` pulls.
+    workspace_configuration: bool,
+    /// Whether the client declared `window.workDoneProgress` support.
+    /// `window/showMessage` has no capability bit of its own, but this is
+    /// the closest real signal that the client wants progress-style
+    /// notifications like "Linting..." rather than just being any client
+    /// that happens to send a `window` capabilities block (nearly all of
+    /// them do).
+    show_progress_notifications: bool,
+}
+
+/// A comment or string-literal node found by Tree-sitter, parsed as its own
+/// [`Document`] so its prose can be spell-checked independently of the
+/// surrounding code.
+struct ProseRegion {
+    /// Offset, in characters, of the region's first character within the
+    /// file's full content. Lint spans produced against `document` are
+    /// relative to the region and must be shifted by this amount before
+    /// they're meaningful against the original file.
+    offset: usize,
+    document: Document,
 }
 
 impl Backend {
@@ -36,10 +163,17 @@ impl Backend {
             // TODO: Proper error handling here.
             return;
         };
-        self.update_document(url, &content).await;
+        self.update_document(url, &content, None).await;
     }
 
-    async fn update_document(&self, url: &Url, text: &str) {
+    // NOTE: this still does a full `Document::new(text, ...)` reparse on
+    // every edit rather than re-tokenizing only the dirty region -
+    // incremental here covers transport (range-based edits over the wire)
+    // but not reparsing. Making the latter incremental too would need
+    // `Document` itself to expose a way to re-tokenize a span and splice
+    // the result into its existing token stream, which is a bigger change
+    // than this fix.
+    async fn update_document(&self, url: &Url, text: &str, version: Option<i32>) {
         let doc = if let Some(extension) = url.to_file_path().unwrap().extension() {
             if let Some(ts_parser) =
                 TreeSitterParser::new_from_extension(&extension.to_string_lossy())
@@ -51,37 +185,132 @@ impl Backend {
                     ident_dicts.insert(url.clone(), new_dict.into());
                 }
 
+                let prose_regions = ts_parser
+                    .create_prose_regions(text)
+                    .into_iter()
+                    .map(|(offset, prose)| ProseRegion {
+                        offset,
+                        document: Document::new(&prose, Box::new(Markdown)),
+                    })
+                    .collect();
+                self.prose_regions
+                    .lock()
+                    .await
+                    .insert(url.clone(), prose_regions);
+
                 doc
             } else {
+                self.prose_regions.lock().await.remove(url);
                 Document::new(text, Box::new(Markdown))
             }
         } else {
+            self.prose_regions.lock().await.remove(url);
             Document::new(text, Box::new(Markdown))
         };
 
         let mut files = self.files.lock().await;
-        files.insert(url.clone(), doc);
+        let prior_version = files
+            .get(url)
+            .map(|open_file| open_file.version)
+            .unwrap_or(0);
+
+        files.insert(
+            url.clone(),
+            OpenFile {
+                source: text.chars().collect(),
+                document: doc,
+                version: version.unwrap_or(prior_version),
+            },
+        );
+    }
+
+    /// Applies each `TextDocumentContentChangeEvent` in turn, splicing ranged
+    /// edits into the buffer we already have instead of requiring the client
+    /// to resend the full document on every keystroke.
+    async fn apply_content_changes(
+        &self,
+        url: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) {
+        // Held for the full read-splice-write below so a concurrent
+        // `did_change` for the same document can't read `files` before this
+        // one writes its result back.
+        let _edit_guard = self.edit_lock.lock().await;
+
+        let mut source = match self.files.lock().await.get(url) {
+            Some(open_file) => open_file.source.clone(),
+            None => Vec::new(),
+        };
+
+        for change in changes {
+            apply_single_change(&mut source, change);
+        }
+
+        let text: String = source.into_iter().collect();
+        self.update_document(url, &text, Some(version)).await;
+    }
+
+    async fn set_document_version(&self, url: &Url, version: i32) {
+        if let Some(open_file) = self.files.lock().await.get_mut(url) {
+            open_file.version = version;
+        }
+    }
+
+    fn extension_of(url: &Url) -> Option<String> {
+        let path = url.to_file_path().ok()?;
+        Some(path.extension()?.to_string_lossy().into_owned())
     }
 
     async fn create_linter(&self, url: &Url) -> LintSet {
         let mut dictionary = MergedDictionary::new();
-        dictionary.add_dictionary(self.global_dictionary.clone());
+        dictionary.add_dictionary(self.global_dictionary.lock().await.clone());
 
         if let Some(ident_dict) = self.ident_dicts.lock().await.get(url) {
             dictionary.add_dictionary(ident_dict.clone());
         };
 
-        LintSet::new().with_standard(dictionary)
+        dictionary.add_dictionary(self.user_dictionary.lock().await.clone());
+
+        let extension = Self::extension_of(url);
+        let config = self.config.lock().await;
+        let lang_config = config.for_extension(extension.as_deref());
+
+        let lint_set = LintSet::new().with_standard(dictionary);
+
+        match lang_config {
+            Some(lang_config) => lint_set.filter_kinds(|kind| lang_config.is_enabled(kind)),
+            None => lint_set,
+        }
     }
 
     async fn generate_code_actions(&self, url: &Url, range: Range) -> Result<Vec<CodeAction>> {
         let files = self.files.lock().await;
-        let Some(document) = files.get(url) else {
+        let Some(document) = files.get(url).map(|open_file| &open_file.document) else {
             return Ok(vec![]);
         };
 
         let mut linter = self.create_linter(url).await;
         let mut lints = linter.lint(document);
+
+        // Also offer actions for lints against Tree-sitter-extracted comment
+        // and string prose (see `generate_diagnostics`, which already
+        // publishes these as diagnostics); shift each one's span by the
+        // region's offset so it lines up with `document`'s coordinates.
+        if let Some(prose_regions) = self.prose_regions.lock().await.get(url) {
+            for region in prose_regions {
+                let region_lints = linter.lint(&region.document).into_iter().map(|mut lint| {
+                    lint.span = Span {
+                        start: lint.span.start + region.offset,
+                        end: lint.span.end + region.offset,
+                    };
+                    lint
+                });
+
+                lints.extend(region_lints);
+            }
+        }
+
         lints.sort_by_key(|l| l.priority);
 
         let source_chars = document.get_full_content();
@@ -89,57 +318,256 @@ impl Backend {
         // Find lints whose span overlaps with range
         let span = range_to_span(source_chars, range);
 
-        let actions = lints
-            .into_iter()
+        let mut actions: Vec<CodeAction> = lints
+            .iter()
             .filter(|lint| lint.span.overlaps_with(span))
-            .flat_map(|lint| lint_to_code_actions(&lint, url, source_chars).collect::<Vec<_>>())
+            .flat_map(|lint| lint_to_code_actions(lint, url, source_chars).collect::<Vec<_>>())
             .collect();
 
+        actions.extend(
+            lints
+                .iter()
+                .filter(|lint| lint.span.overlaps_with(span))
+                .filter(|lint| lint.lint_kind == LintKind::Spelling)
+                .map(|lint| Self::add_to_dictionary_action(document, lint)),
+        );
+
         Ok(actions)
     }
 
+    /// Builds the "Add '<word>' to dictionary." code action for a spelling
+    /// lint. Because code actions can't write files directly, the actual
+    /// persistence happens in [`Backend::execute_command`] once the client
+    /// runs the attached command.
+    fn add_to_dictionary_action(document: &Document, lint: &Lint) -> CodeAction {
+        let word = document.get_span_content_str(lint.span);
+
+        CodeAction {
+            title: format!("Add '{word}' to dictionary."),
+            kind: Some(CodeActionKind::QUICKFIX),
+            command: Some(Command {
+                title: format!("Add '{word}' to dictionary."),
+                command: ADD_TO_DICTIONARY_COMMAND.to_string(),
+                arguments: Some(vec![serde_json::Value::String(word)]),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn user_dictionary_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("harper-ls")
+            .join("dictionary.txt")
+    }
+
+    async fn load_user_dictionary() -> FullDictionary {
+        match tokio::fs::read_to_string(Self::user_dictionary_path()).await {
+            Ok(contents) => FullDictionary::create_from_iter(contents.lines()),
+            Err(_) => FullDictionary::create_from_iter(std::iter::empty::<&str>()),
+        }
+    }
+
+    /// Appends `word` to the user dictionary file and merges it into the
+    /// in-memory dictionary used by every linter going forward.
+    ///
+    /// Holds `user_dictionary_write_lock` across the already-present check
+    /// and the append below so two concurrent invocations for the same word
+    /// can't both see it as absent and both append it, reintroducing the
+    /// duplicate lines that check exists to avoid.
+    async fn add_to_dictionary(&self, word: &str) {
+        let word = word.trim();
+        if word.is_empty() {
+            return;
+        }
+
+        let _write_guard = self.user_dictionary_write_lock.lock().await;
+
+        let path = Self::user_dictionary_path();
+
+        if let Some(parent) = path.parent() {
+            if let Err(err) = tokio::fs::create_dir_all(parent).await {
+                self.client
+                    .log_message(
+                        MessageType::ERROR,
+                        format!("Failed to create user dictionary directory: {err}"),
+                    )
+                    .await;
+                return;
+            }
+        }
+
+        // Skip words the file already has so repeat "Add to dictionary"
+        // invocations don't pile up duplicate lines.
+        let already_present = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => contents.lines().any(|line| line.trim() == word),
+            Err(_) => false,
+        };
+
+        if already_present {
+            return;
+        }
+
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await;
+
+        let mut file = match file {
+            Ok(file) => file,
+            Err(err) => {
+                self.client
+                    .log_message(
+                        MessageType::ERROR,
+                        format!("Failed to open user dictionary: {err}"),
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        if let Err(err) = file.write_all(format!("{word}\n").as_bytes()).await {
+            self.client
+                .log_message(
+                    MessageType::ERROR,
+                    format!("Failed to persist word to user dictionary: {err}"),
+                )
+                .await;
+            return;
+        }
+
+        *self.user_dictionary.lock().await = Self::load_user_dictionary().await.into();
+
+        self.relint_all_open_files().await;
+    }
+
     pub fn new(client: Client) -> Self {
         let dictionary = FullDictionary::create_from_curated();
 
         Self {
             client,
-            global_dictionary: dictionary.into(),
+            global_dictionary: Mutex::new(dictionary.into()),
             files: Mutex::new(HashMap::new()),
             ident_dicts: Mutex::new(HashMap::new()),
+            prose_regions: Mutex::new(HashMap::new()),
+            user_dictionary: Mutex::new(
+                FullDictionary::create_from_iter(std::iter::empty::<&str>()).into(),
+            ),
+            edit_lock: Mutex::new(()),
+            user_dictionary_write_lock: Mutex::new(()),
+            config: Mutex::new(Config::default()),
+            client_capabilities: Mutex::new(ClientCapabilities::default()),
         }
     }
 
+    async fn relint_all_open_files(&self) {
+        let urls: Vec<Url> = self.files.lock().await.keys().cloned().collect();
+
+        for url in urls {
+            self.publish_diagnostics(&url).await;
+        }
+    }
+
+    /// Rebuild the global dictionary from the curated word list and re-derive
+    /// every open file's identifier dictionary, picking up any out-of-band
+    /// changes (e.g. edits to a user word list) without a server restart.
+    ///
+    /// Re-derives from each file's in-memory `source` rather than re-reading
+    /// from disk, since a buffer can have unsaved edits that incremental
+    /// sync (see `apply_content_changes`) already folded in; re-reading the
+    /// file here would silently revert Harper's view of it.
+    ///
+    /// Takes `edit_lock` across the snapshot-and-writeback below for the same
+    /// reason `apply_content_changes` does: without it, a concurrent
+    /// `did_change` could land between the snapshot and `update_document`
+    /// writing it back, and this call would then overwrite `files[url]` with
+    /// the stale pre-edit text, dropping the user's keystrokes.
+    async fn reload_dictionaries(&self) {
+        let _edit_guard = self.edit_lock.lock().await;
+
+        *self.global_dictionary.lock().await = FullDictionary::create_from_curated().into();
+        *self.user_dictionary.lock().await = Self::load_user_dictionary().await.into();
+
+        self.ident_dicts.lock().await.clear();
+
+        let sources: Vec<(Url, String)> = self
+            .files
+            .lock()
+            .await
+            .iter()
+            .map(|(url, open_file)| (url.clone(), open_file.source.iter().collect()))
+            .collect();
+
+        for (url, text) in &sources {
+            self.update_document(url, text, None).await;
+        }
+
+        self.relint_all_open_files().await;
+    }
+
     async fn generate_diagnostics(&self, url: &Url) -> Vec<Diagnostic> {
         let files = self.files.lock().await;
 
-        let Some(document) = files.get(url) else {
+        let Some(document) = files.get(url).map(|open_file| &open_file.document) else {
             return vec![];
         };
 
         let mut linter = self.create_linter(url).await;
-        let lints = linter.lint(document);
+        let mut lints = linter.lint(document);
+
+        if let Some(prose_regions) = self.prose_regions.lock().await.get(url) {
+            for region in prose_regions {
+                let region_lints = linter.lint(&region.document).into_iter().map(|mut lint| {
+                    lint.span = Span {
+                        start: lint.span.start + region.offset,
+                        end: lint.span.end + region.offset,
+                    };
+                    lint
+                });
+
+                lints.extend(region_lints);
+            }
+        }
 
         lints_to_diagnostics(document.get_full_content(), &lints)
     }
 
     async fn publish_diagnostics(&self, url: &Url) {
-        let client = self.client.clone();
-
-        tokio::spawn(async move {
-            client
-                .send_notification::<ShowMessage>(ShowMessageParams {
-                    typ: MessageType::INFO,
-                    message: "Linting...".to_string(),
-                })
-                .await
-        });
+        if self
+            .client_capabilities
+            .lock()
+            .await
+            .show_progress_notifications
+        {
+            let client = self.client.clone();
+
+            tokio::spawn(async move {
+                client
+                    .send_notification::<ShowMessage>(ShowMessageParams {
+                        typ: MessageType::INFO,
+                        message: "Linting...".to_string(),
+                    })
+                    .await
+            });
+        }
 
         let diagnostics = self.generate_diagnostics(url).await;
 
+        let version = if self.client_capabilities.lock().await.diagnostic_versioning {
+            self.files
+                .lock()
+                .await
+                .get(url)
+                .map(|open_file| open_file.version)
+        } else {
+            None
+        };
+
         let result = PublishDiagnosticsParams {
             uri: url.clone(),
             diagnostics,
-            version: None,
+            version,
         };
 
         self.client
@@ -148,22 +576,100 @@ impl Backend {
     }
 }
 
+/// Applies a single `TextDocumentContentChangeEvent` to `source` in place:
+/// splices the changed range if the client sent one (incremental sync), or
+/// replaces the whole buffer if it didn't (a full-text change, e.g. the
+/// client's first `did_change` after falling back from incremental sync).
+/// Pulled out of `Backend::apply_content_changes` so the splicing math can
+/// be unit tested without an `&self`.
+fn apply_single_change(source: &mut Vec<char>, change: TextDocumentContentChangeEvent) {
+    match change.range {
+        Some(range) => {
+            let span = range_to_span(source, range);
+            source.splice(span.start..span.end, change.text.chars());
+        }
+        None => {
+            *source = change.text.chars().collect();
+        }
+    }
+}
+
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        let code_actions = params
+            .capabilities
+            .text_document
+            .as_ref()
+            .and_then(|td| td.code_action.as_ref())
+            .is_some();
+
+        let diagnostic_versioning = params
+            .capabilities
+            .text_document
+            .as_ref()
+            .and_then(|td| td.publish_diagnostics.as_ref())
+            .and_then(|pd| pd.version_support)
+            .unwrap_or(false);
+
+        let workspace_configuration = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.configuration)
+            .unwrap_or(false);
+
+        let show_progress_notifications = params
+            .capabilities
+            .window
+            .as_ref()
+            .and_then(|w| w.work_done_progress)
+            .unwrap_or(false);
+
+        *self.client_capabilities.lock().await = ClientCapabilities {
+            code_actions,
+            diagnostic_versioning,
+            workspace_configuration,
+            show_progress_notifications,
+        };
+
+        if let Some(options) = params.initialization_options {
+            match serde_json::from_value(options) {
+                Ok(parsed) => *self.config.lock().await = parsed,
+                Err(err) => {
+                    self.client
+                        .log_message(
+                            MessageType::WARNING,
+                            format!("Failed to parse initializationOptions: {err}"),
+                        )
+                        .await;
+                }
+            }
+        }
+
+        *self.user_dictionary.lock().await = Self::load_user_dictionary().await.into();
+
         Ok(InitializeResult {
             server_info: None,
             capabilities: ServerCapabilities {
-                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                code_action_provider: code_actions
+                    .then_some(CodeActionProviderCapability::Simple(true)),
                 text_document_sync: Some(TextDocumentSyncCapability::Options(
                     TextDocumentSyncOptions {
                         open_close: Some(true),
-                        change: Some(TextDocumentSyncKind::FULL),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
                         will_save: None,
                         will_save_wait_until: None,
                         save: Some(TextDocumentSyncSaveOptions::Supported(true)),
                     },
                 )),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![
+                        RELOAD_DICTIONARIES_COMMAND.to_string(),
+                        ADD_TO_DICTIONARY_COMMAND.to_string(),
+                    ],
+                    ..Default::default()
+                }),
                 ..Default::default()
             },
         })
@@ -173,6 +679,48 @@ impl LanguageServer for Backend {
         self.client
             .log_message(MessageType::INFO, "Server initialized!")
             .await;
+
+        if !self
+            .client_capabilities
+            .lock()
+            .await
+            .workspace_configuration
+        {
+            return;
+        }
+
+        let items = vec![ConfigurationItem {
+            scope_uri: None,
+            section: Some("harper".to_string()),
+        }];
+
+        match self.client.configuration(items).await {
+            Ok(values) => {
+                if let Some(value) = values.into_iter().next() {
+                    match serde_json::from_value(value) {
+                        Ok(parsed) => *self.config.lock().await = parsed,
+                        Err(err) => {
+                            self.client
+                                .log_message(
+                                    MessageType::WARNING,
+                                    format!(
+                                        "Failed to parse workspace/configuration response: {err}"
+                                    ),
+                                )
+                                .await;
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("workspace/configuration request failed: {err}"),
+                    )
+                    .await;
+            }
+        }
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -196,24 +744,43 @@ impl LanguageServer for Backend {
 
         self.update_document_from_file(&params.text_document.uri)
             .await;
+        self.set_document_version(&params.text_document.uri, params.text_document.version)
+            .await;
 
         self.publish_diagnostics(&params.text_document.uri).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        let Some(last) = params.content_changes.last() else {
-            return;
-        };
-
         self.client
             .log_message(MessageType::INFO, "File changed!")
             .await;
 
-        self.update_document(&params.text_document.uri, &last.text)
-            .await;
+        self.apply_content_changes(
+            &params.text_document.uri,
+            params.content_changes,
+            params.text_document.version,
+        )
+        .await;
         self.publish_diagnostics(&params.text_document.uri).await;
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        match serde_json::from_value(params.settings) {
+            Ok(parsed) => {
+                *self.config.lock().await = parsed;
+                self.relint_all_open_files().await;
+            }
+            Err(err) => {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("Failed to parse didChangeConfiguration settings: {err}"),
+                    )
+                    .await;
+            }
+        }
+    }
+
     async fn did_close(&self, _params: DidCloseTextDocumentParams) {
         self.client
             .log_message(MessageType::INFO, "File closed!")
@@ -236,4 +803,166 @@ impl LanguageServer for Backend {
                 .collect(),
         ))
     }
+
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        match params.command.as_str() {
+            RELOAD_DICTIONARIES_COMMAND => {
+                self.reload_dictionaries().await;
+                Ok(None)
+            }
+            ADD_TO_DICTIONARY_COMMAND => {
+                let Some(word) = params.arguments.first().and_then(|v| v.as_str()) else {
+                    self.client
+                        .log_message(
+                            MessageType::WARNING,
+                            format!("{ADD_TO_DICTIONARY_COMMAND} called without a word argument"),
+                        )
+                        .await;
+                    return Ok(None);
+                };
+
+                self.add_to_dictionary(word).await;
+                Ok(None)
+            }
+            other => {
+                self.client
+                    .log_message(MessageType::WARNING, format!("Unknown command: {other}"))
+                    .await;
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tower_lsp::lsp_types::Position;
+
+    use super::*;
+
+    fn change(
+        start: (u32, u32),
+        end: (u32, u32),
+        text: &str,
+    ) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position {
+                    line: start.0,
+                    character: start.1,
+                },
+                end: Position {
+                    line: end.0,
+                    character: end.1,
+                },
+            }),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn splices_a_single_line_range() {
+        let mut source: Vec<char> = "hello world".chars().collect();
+
+        apply_single_change(&mut source, change((0, 6), (0, 11), "there"));
+
+        assert_eq!(source.into_iter().collect::<String>(), "hello there");
+    }
+
+    #[test]
+    fn splices_a_range_spanning_multiple_lines() {
+        let mut source: Vec<char> = "first\nsecond\nthird".chars().collect();
+
+        // Replace from the middle of "second" through the middle of "third".
+        apply_single_change(&mut source, change((1, 3), (2, 2), "ond-new-th"));
+
+        assert_eq!(
+            source.into_iter().collect::<String>(),
+            "first\nsecond-new-third"
+        );
+    }
+
+    #[test]
+    fn splices_after_a_multi_byte_character() {
+        // "café" is 4 chars but "é" is 2 bytes in UTF-8 and, more to the
+        // point for LSP, 1 UTF-16 code unit - range_to_span needs to land
+        // on the right *char* index regardless of how the client's
+        // Position.character counts got there.
+        let mut source: Vec<char> = "café".chars().collect();
+
+        apply_single_change(&mut source, change((0, 4), (0, 4), "!"));
+
+        assert_eq!(source.into_iter().collect::<String>(), "café!");
+    }
+
+    #[test]
+    fn inserting_at_an_empty_range_does_not_delete_anything() {
+        let mut source: Vec<char> = "ab".chars().collect();
+
+        apply_single_change(&mut source, change((0, 1), (0, 1), "-"));
+
+        assert_eq!(source.into_iter().collect::<String>(), "a-b");
+    }
+
+    #[test]
+    fn neither_only_nor_except_set_enables_everything() {
+        let config = LanguageLintConfig::default();
+
+        assert!(config.is_enabled(LintKind::Spelling));
+        assert!(config.is_enabled(LintKind::Capitalization));
+    }
+
+    #[test]
+    fn only_lints_enables_just_the_listed_kinds() {
+        let config = LanguageLintConfig {
+            only_lints: Some([LintKind::Spelling].into_iter().collect()),
+            except_lints: None,
+        };
+
+        assert!(config.is_enabled(LintKind::Spelling));
+        assert!(!config.is_enabled(LintKind::Capitalization));
+    }
+
+    #[test]
+    fn except_lints_disables_just_the_listed_kinds() {
+        let config = LanguageLintConfig {
+            only_lints: None,
+            except_lints: Some([LintKind::Capitalization].into_iter().collect()),
+        };
+
+        assert!(config.is_enabled(LintKind::Spelling));
+        assert!(!config.is_enabled(LintKind::Capitalization));
+    }
+
+    #[test]
+    fn only_lints_takes_precedence_over_except_lints() {
+        // A kind listed in both: only_lints should win, per the only_lints
+        // field's doc comment, even though except_lints alone would disable it.
+        let config = LanguageLintConfig {
+            only_lints: Some([LintKind::Spelling].into_iter().collect()),
+            except_lints: Some([LintKind::Spelling].into_iter().collect()),
+        };
+
+        assert!(config.is_enabled(LintKind::Spelling));
+    }
+
+    #[test]
+    fn a_change_with_no_range_replaces_the_whole_buffer() {
+        let mut source: Vec<char> = "old content".chars().collect();
+
+        apply_single_change(
+            &mut source,
+            TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "new content".to_string(),
+            },
+        );
+
+        assert_eq!(source.into_iter().collect::<String>(), "new content");
+    }
 }
@@ -0,0 +1,89 @@
+use crate::tree_sitter_parser::TreeSitterParser;
+
+impl TreeSitterParser {
+    /// Finds every comment and string-literal node in `text` and returns its
+    /// text alongside its **char** offset into `text` (not a byte offset —
+    /// `Backend::generate_diagnostics` adds this directly to char-indexed lint
+    /// spans, so a byte offset would misplace every diagnostic in a file
+    /// with any multi-byte characters).
+    ///
+    /// Matches on any node kind containing `comment` or `string` rather than
+    /// a per-grammar query; those substrings are conventional across
+    /// Tree-sitter grammars (`comment`, `line_comment`, `string_literal`,
+    /// …), but this is a heuristic, not full grammar coverage: a grammar
+    /// whose comment/string node kinds don't follow that naming convention
+    /// is silently missed, and sub-nodes that coincidentally contain these
+    /// substrings without being prose (e.g. some grammars' string-escape or
+    /// interpolation nodes) are silently included. A real fix is a query
+    /// set per grammar, as the backlog originally asked for; this string
+    /// match is a stand-in until one exists.
+    pub fn create_prose_regions(&self, text: &str) -> Vec<(usize, String)> {
+        let mut parser = tree_sitter::Parser::new();
+
+        if parser.set_language(&self.language()).is_err() {
+            return Vec::new();
+        }
+
+        let Some(tree) = parser.parse(text, None) else {
+            return Vec::new();
+        };
+
+        let mut regions = Vec::new();
+        let mut cursor = tree.root_node().walk();
+        let mut stack = vec![tree.root_node()];
+
+        while let Some(node) = stack.pop() {
+            let kind = node.kind();
+
+            if kind.contains("comment") || kind.contains("string") {
+                let Ok(content) = node.utf8_text(text.as_bytes()) else {
+                    continue;
+                };
+
+                let char_offset = byte_to_char_offset(text, node.start_byte());
+
+                regions.push((char_offset, content.to_string()));
+                continue;
+            }
+
+            stack.extend(node.children(&mut cursor));
+        }
+
+        regions
+    }
+}
+
+/// Converts a Tree-sitter byte offset into `text` to the equivalent char
+/// offset, since lint spans (and the offsets `create_prose_regions` adds
+/// them to) are char-indexed, not byte-indexed.
+fn byte_to_char_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::byte_to_char_offset;
+
+    #[test]
+    fn ascii_byte_and_char_offsets_match() {
+        let text = "fn main() {}";
+        let byte_offset = text.find("main").unwrap();
+
+        assert_eq!(byte_to_char_offset(text, byte_offset), byte_offset);
+    }
+
+    #[test]
+    fn multi_byte_prefix_shrinks_the_offset() {
+        // "é" is 2 bytes but 1 char, so the byte offset of "world" overcounts
+        // by one relative to its char offset.
+        let text = "café world";
+        let byte_offset = text.find("world").unwrap();
+
+        assert_eq!(byte_to_char_offset(text, byte_offset), byte_offset - 1);
+    }
+
+    #[test]
+    fn offset_at_start_of_string_is_zero() {
+        assert_eq!(byte_to_char_offset("hello", 0), 0);
+    }
+}